@@ -4,11 +4,16 @@ use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // --- Category ---
 
 const BUILTIN_CATEGORIES: &[&str] = &["work", "personal", "shopping", "health"];
 
+const COMMANDS: &[&str] = &[
+    "add", "list", "due", "done", "prio", "remove", "category", "export", "import",
+];
+
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 enum Category {
@@ -48,12 +53,226 @@ fn parse_category(name: &str, custom_categories: &[String]) -> Result<Category,
                     .unwrap();
                 Ok(Category::Custom(stored.clone()))
             } else {
-                Err(format!("Unknown category: {name}"))
+                let mut candidates: Vec<&str> = BUILTIN_CATEGORIES.to_vec();
+                candidates.extend(custom_categories.iter().map(String::as_str));
+                match did_you_mean(name, candidates) {
+                    Some(suggestion) => Err(format!(
+                        "Unknown category: {name}\nDid you mean '{suggestion}'?"
+                    )),
+                    None => Err(format!("Unknown category: {name}")),
+                }
             }
         }
     }
 }
 
+// --- Due dates ---
+
+fn now_ts() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before epoch")
+        .as_secs() as i64
+}
+
+/// Round a timestamp down to midnight UTC of the same day.
+fn midnight(ts: i64) -> i64 {
+    ts - ts.rem_euclid(86400)
+}
+
+/// Days since 1970-01-01 for a given (year, month, day), using Howard Hinnant's
+/// days_from_civil algorithm. Dates are treated as UTC.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn is_leap_year(y: i64) -> bool {
+    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(y) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Parse an absolute date like `2024-06-01` or `2024-06-01T09:00` into a Unix timestamp.
+fn parse_absolute_date(s: &str) -> Option<i64> {
+    let (date_part, time_part) = match s.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (s, None),
+    };
+    let fields: Vec<&str> = date_part.split('-').collect();
+    if fields.len() != 3 {
+        return None;
+    }
+    let year: i64 = fields[0].parse().ok()?;
+    let month: i64 = fields[1].parse().ok()?;
+    let day: i64 = fields[2].parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut secs = days_from_civil(year, month, day) * 86400;
+    if let Some(t) = time_part {
+        let hm: Vec<&str> = t.split(':').collect();
+        if hm.is_empty() || hm.len() > 2 {
+            return None;
+        }
+        let hour: i64 = hm[0].parse().ok()?;
+        let minute: i64 = hm.get(1).map(|m| m.parse()).transpose().ok()?.unwrap_or(0);
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) {
+            return None;
+        }
+        secs += hour * 3600 + minute * 60;
+    }
+    Some(secs)
+}
+
+/// Map a unit word (`s`, `m`, `h`, `d`, `w`, or longer forms sharing the same
+/// first letter) to its length in seconds.
+fn unit_seconds(unit: &str) -> Result<i64, String> {
+    match unit.chars().next() {
+        Some('s') => Ok(1),
+        Some('m') => Ok(60),
+        Some('h') => Ok(3600),
+        Some('d') => Ok(86400),
+        Some('w') => Ok(604800),
+        _ => Err(format!("Unknown time unit: {unit}")),
+    }
+}
+
+/// Parse a duration expression like `2h`, `3 days`, or `in 3 days` into seconds.
+fn parse_duration(expr: &str) -> Result<i64, String> {
+    let lower = expr.trim().to_lowercase();
+    let lower = lower.strip_prefix("in ").unwrap_or(&lower);
+    let tokens: Vec<&str> = lower.split_whitespace().collect();
+    if tokens.is_empty() {
+        return Err(format!("Invalid duration: {expr}"));
+    }
+    let mut total = 0i64;
+    let mut i = 0;
+    while i < tokens.len() {
+        let tok = tokens[i];
+        let split_at = tok.find(|c: char| !c.is_ascii_digit());
+        let (num, unit) = match split_at {
+            Some(0) | None => {
+                // The whole token is digits with no unit attached: the unit must
+                // be the next token. A token that does not start with a digit
+                // fails to parse as a number and is rejected.
+                let num: i64 = tok
+                    .parse()
+                    .map_err(|_| format!("Invalid duration: {expr}"))?;
+                i += 1;
+                let unit = tokens
+                    .get(i)
+                    .ok_or_else(|| format!("Invalid duration: {expr}"))?;
+                (num, *unit)
+            }
+            Some(idx) => {
+                let num: i64 = tok[..idx]
+                    .parse()
+                    .map_err(|_| format!("Invalid duration: {expr}"))?;
+                (num, &tok[idx..])
+            }
+        };
+        total += num * unit_seconds(unit)?;
+        i += 1;
+    }
+    Ok(total)
+}
+
+/// Parse a due-date expression: absolute dates, or relative forms like
+/// `in 2h`, `in 3 days`, `tomorrow`, `next week`, `next 3 days`.
+fn parse_due(expr: &str) -> Result<i64, String> {
+    let lower = expr.trim().to_lowercase();
+    match lower.as_str() {
+        "today" => Ok(midnight(now_ts())),
+        "tomorrow" => Ok(midnight(now_ts()) + 86400),
+        "next week" => Ok(now_ts() + 7 * 86400),
+        _ if lower.starts_with("in ") => Ok(now_ts() + parse_duration(&lower)?),
+        _ if lower.starts_with("next ") => Ok(now_ts() + parse_duration(&lower[5..])?),
+        _ => parse_absolute_date(expr.trim())
+            .ok_or_else(|| format!("Invalid due date or duration: {expr}")),
+    }
+}
+
+/// Render a duration in seconds as a short human label, e.g. `3h`, `2d`.
+fn format_duration(secs: i64) -> String {
+    let secs = secs.abs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else if secs < 86400 {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}d", secs / 86400)
+    }
+}
+
+/// Render the `(due in 3h)` / `(OVERDUE 2d)` suffix for a todo's due date, if any.
+fn format_due_suffix(due: Option<i64>) -> String {
+    match due {
+        None => String::new(),
+        Some(due) => {
+            let diff = due - now_ts();
+            if diff < 0 {
+                format!(" (OVERDUE {})", format_duration(diff))
+            } else {
+                format!(" (due in {})", format_duration(diff))
+            }
+        }
+    }
+}
+
+// --- Fuzzy matching ---
+
+/// Levenshtein edit distance, computed with a single rolling row (O(min(m,n)) space).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (long, short) = if a.chars().count() >= b.chars().count() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let short: Vec<char> = short.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=short.len()).collect();
+    for lc in long.chars() {
+        let mut row = vec![0usize; short.len() + 1];
+        row[0] = prev_row[0] + 1;
+        for (j, &sc) in short.iter().enumerate() {
+            let cost = if lc == sc { 0 } else { 1 };
+            row[j + 1] = (prev_row[j + 1] + 1)
+                .min(row[j] + 1)
+                .min(prev_row[j] + cost);
+        }
+        prev_row = row;
+    }
+    prev_row[short.len()]
+}
+
+/// Find the closest candidate to `input` by edit distance, if it's within a
+/// reasonable threshold (`<= 2` or `<= input.len() / 2`).
+fn did_you_mean<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let lower = input.to_lowercase();
+    let threshold = (lower.chars().count() / 2).max(2);
+    candidates
+        .into_iter()
+        .map(|c| (c, edit_distance(&lower, &c.to_lowercase())))
+        .min_by_key(|&(_, dist)| dist)
+        .filter(|&(_, dist)| dist <= threshold)
+        .map(|(c, _)| c)
+}
+
 // --- Data paths ---
 
 fn home_dir() -> PathBuf {
@@ -106,14 +325,26 @@ struct Todo {
     done: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     category: Option<Category>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<u8>,
 }
 
-fn load_todos() -> Vec<Todo> {
-    load_json(&todos_path())
+/// Parse a priority level, which must be 1 (low) to 3 (high).
+fn parse_priority(s: &str) -> Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(n) if (1..=3).contains(&n) => Ok(n),
+        _ => Err(format!("Invalid priority: {s} (must be 1, 2, or 3)")),
+    }
 }
 
-fn save_todos(todos: &[Todo]) {
-    save_json(&todos_path(), &todos.to_vec());
+/// Render a priority as `!`, `!!`, or `!!!`.
+fn priority_marker(priority: Option<u8>) -> String {
+    match priority {
+        Some(n @ 1..=3) => "!".repeat(n as usize),
+        _ => String::new(),
+    }
 }
 
 fn load_custom_categories() -> Vec<String> {
@@ -124,66 +355,591 @@ fn save_custom_categories(cats: &[String]) {
     save_json(&categories_path(), &cats.to_vec());
 }
 
-fn next_id(todos: &[Todo]) -> u32 {
-    todos.iter().map(|t| t.id).max().unwrap_or(0) + 1
+// --- Todo lists ---
+
+const DEFAULT_LIST: &str = "inbox";
+
+#[derive(Serialize, Deserialize, Clone)]
+struct TodoList {
+    name: String,
+    todos: Vec<Todo>,
+    next_id: u32,
+}
+
+impl TodoList {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            todos: Vec::new(),
+            next_id: 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Store {
+    lists: Vec<TodoList>,
+}
+
+impl Store {
+    fn find(&self, name: &str) -> Option<&TodoList> {
+        self.lists.iter().find(|l| l.name == name)
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut TodoList> {
+        self.lists.iter_mut().find(|l| l.name == name)
+    }
+}
+
+fn active_list_path() -> PathBuf {
+    home_dir().join(".todo_active.json")
+}
+
+#[derive(Serialize, Deserialize)]
+struct ActiveState {
+    list: String,
+}
+
+impl Default for ActiveState {
+    fn default() -> Self {
+        Self {
+            list: DEFAULT_LIST.to_string(),
+        }
+    }
+}
+
+fn active_list_name() -> String {
+    load_json::<ActiveState>(&active_list_path()).list
+}
+
+fn set_active_list_name(name: &str) {
+    save_json(
+        &active_list_path(),
+        &ActiveState {
+            list: name.to_string(),
+        },
+    );
+}
+
+/// Load the todo store, migrating a pre-multi-list `~/.todos.json` (a flat
+/// `Vec<Todo>`) into a single `inbox` list on first run.
+fn load_store() -> Store {
+    let path = todos_path();
+    if !path.exists() {
+        return Store {
+            lists: vec![TodoList::new(DEFAULT_LIST)],
+        };
+    }
+    let data = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("Error reading {}: {e}", path.display());
+        process::exit(1);
+    });
+    if let Ok(store) = serde_json::from_str::<Store>(&data) {
+        return store;
+    }
+    let legacy: Vec<Todo> = serde_json::from_str(&data).unwrap_or_else(|e| {
+        eprintln!("Error parsing {}: {e}", path.display());
+        process::exit(1);
+    });
+    let next_id = legacy.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+    Store {
+        lists: vec![TodoList {
+            name: DEFAULT_LIST.to_string(),
+            todos: legacy,
+            next_id,
+        }],
+    }
+}
+
+fn save_store(store: &Store) {
+    save_json(&todos_path(), store);
+}
+
+fn get_list_mut<'a>(store: &'a mut Store, name: &str, json: bool) -> &'a mut TodoList {
+    if store.find(name).is_none() {
+        fail(
+            json,
+            format!("No such list: '{name}'. Use 'todo list new {name}' to create it."),
+        );
+    }
+    store.find_mut(name).unwrap()
+}
+
+// --- JSON output mode ---
+
+/// A typed result a command can emit, serialized to stdout when `--json` is passed.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Output {
+    Todo(Todo),
+    Todos(Vec<Todo>),
+    Categories {
+        builtin: Vec<String>,
+        custom: Vec<String>,
+    },
+}
+
+fn emit(output: &Output) {
+    println!("{}", serde_json::to_string(output).expect("serialize"));
+}
+
+/// Report an error either as plain text on stderr, or as a `{"error": "..."}`
+/// object on stdout when running in `--json` mode, then exit(1).
+fn fail(json: bool, message: impl Into<String>) -> ! {
+    let message = message.into();
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&serde_json::json!({ "error": message })).expect("serialize")
+        );
+    } else {
+        eprintln!("{message}");
+    }
+    process::exit(1);
+}
+
+// --- Import / export ---
+
+enum ExportFormat {
+    Json,
+    Md,
+    Csv,
+}
+
+fn parse_export_format(s: &str) -> Result<ExportFormat, String> {
+    match s.to_lowercase().as_str() {
+        "json" => Ok(ExportFormat::Json),
+        "md" | "markdown" => Ok(ExportFormat::Md),
+        "csv" => Ok(ExportFormat::Csv),
+        _ => Err(format!("Unknown export format: {s}")),
+    }
+}
+
+/// A flat, schema-light view of a `Todo` used for import/export, where the
+/// category is a plain string rather than the `Category` enum.
+#[derive(Serialize, Deserialize)]
+struct TodoRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<u32>,
+    text: String,
+    #[serde(default)]
+    done: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<u8>,
+}
+
+impl From<&Todo> for TodoRecord {
+    fn from(t: &Todo) -> Self {
+        Self {
+            id: Some(t.id),
+            text: t.text.clone(),
+            done: t.done,
+            category: t.category.as_ref().map(|c| c.to_string()),
+            due: t.due,
+            priority: t.priority,
+        }
+    }
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Parse a plain newline-delimited import file. Each non-empty line becomes a
+/// todo, except `## <category>` headings (which set the category for the
+/// lines that follow) and `- [x]`/`- [ ]` checkboxes (which also carry a
+/// done flag) — the shape produced by the Markdown exporter, so exporting
+/// and re-importing round-trips cleanly.
+fn parse_import_text(data: &str) -> Vec<TodoRecord> {
+    let mut records = Vec::new();
+    let mut current_category: Option<String> = None;
+    for line in data.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("## ") {
+            current_category = Some(heading.trim().to_string());
+            continue;
+        }
+        let (done, text) = if let Some(rest) = line.strip_prefix("- [x] ") {
+            (true, rest)
+        } else if let Some(rest) = line.strip_prefix("- [ ] ") {
+            (false, rest)
+        } else {
+            (false, line)
+        };
+        records.push(TodoRecord {
+            id: None,
+            text: text.trim().to_string(),
+            done,
+            category: current_category.clone(),
+            due: None,
+            priority: None,
+        });
+    }
+    records
+}
+
+/// Parse an import file, trying the current/legacy JSON schema first and
+/// falling back to plain newline-delimited text.
+fn parse_import(data: &str) -> Vec<TodoRecord> {
+    serde_json::from_str::<Vec<TodoRecord>>(data).unwrap_or_else(|_| parse_import_text(data))
 }
 
 // --- Commands ---
 
-fn cmd_add(text: &str, category: Option<Category>) {
-    let mut todos = load_todos();
-    let id = next_id(&todos);
-    todos.push(Todo {
+fn cmd_add(
+    list_name: &str,
+    text: &str,
+    category: Option<Category>,
+    due: Option<i64>,
+    priority: Option<u8>,
+    json: bool,
+) {
+    let mut store = load_store();
+    let list = get_list_mut(&mut store, list_name, json);
+    let id = list.next_id;
+    list.next_id += 1;
+    let todo = Todo {
         id,
         text: text.to_string(),
         done: false,
         category: category.clone(),
-    });
-    save_todos(&todos);
-    match category {
-        Some(cat) => println!("Added todo #{id} [{cat}]: {text}"),
-        None => println!("Added todo #{id}: {text}"),
+        due,
+        priority,
+    };
+    list.todos.push(todo.clone());
+    save_store(&store);
+    if json {
+        emit(&Output::Todo(todo));
+        return;
+    }
+    let cat_label = match &category {
+        Some(cat) => format!(" [{cat}]"),
+        None => String::new(),
+    };
+    let prio_label = match priority_marker(priority).as_str() {
+        "" => String::new(),
+        marker => format!(" {marker}"),
+    };
+    println!(
+        "Added todo #{id}{cat_label}{prio_label}{} to '{list_name}': {text}",
+        format_due_suffix(due)
+    );
+}
+
+fn print_todo(t: &Todo) {
+    let mark = if t.done { "x" } else { " " };
+    let cat_label = match &t.category {
+        Some(c) => format!(" [{c}]"),
+        None => String::new(),
+    };
+    let prio_label = match priority_marker(t.priority).as_str() {
+        "" => String::new(),
+        marker => format!(" {marker}"),
+    };
+    println!(
+        "[{mark}] #{}{}{}: {}{}",
+        t.id,
+        cat_label,
+        prio_label,
+        t.text,
+        format_due_suffix(t.due)
+    );
+}
+
+enum SortKey {
+    Id,
+    Due,
+    Prio,
+}
+
+fn parse_sort_key(s: &str) -> Result<SortKey, String> {
+    match s.to_lowercase().as_str() {
+        "id" => Ok(SortKey::Id),
+        "due" => Ok(SortKey::Due),
+        "prio" | "priority" => Ok(SortKey::Prio),
+        _ => Err(format!("Unknown sort key: {s} (expected prio, due, or id)")),
+    }
+}
+
+fn overdue_rank(t: &Todo, now: i64) -> u8 {
+    if t.due.is_some_and(|d| d <= now) {
+        0
+    } else {
+        1
+    }
+}
+
+/// Sort todos by the given key: `id` by id; `due` by due date (undated last),
+/// then higher priority, then lower id; `prio` overdue first, then higher
+/// priority, then lower id.
+fn sort_todos(todos: &mut [&Todo], key: SortKey, now: i64) {
+    match key {
+        SortKey::Id => todos.sort_by_key(|t| t.id),
+        SortKey::Due => todos.sort_by(|a, b| {
+            a.due
+                .unwrap_or(i64::MAX)
+                .cmp(&b.due.unwrap_or(i64::MAX))
+                .then_with(|| b.priority.unwrap_or(0).cmp(&a.priority.unwrap_or(0)))
+                .then_with(|| a.id.cmp(&b.id))
+        }),
+        SortKey::Prio => todos.sort_by(|a, b| {
+            overdue_rank(a, now)
+                .cmp(&overdue_rank(b, now))
+                .then_with(|| b.priority.unwrap_or(0).cmp(&a.priority.unwrap_or(0)))
+                .then_with(|| a.id.cmp(&b.id))
+        }),
     }
 }
 
-fn cmd_list() {
-    let todos = load_todos();
+fn cmd_list(
+    list_name: &str,
+    json: bool,
+    sort: Option<SortKey>,
+    cat_filter: Option<Category>,
+    pending_only: bool,
+    done_only: bool,
+) {
+    let store = load_store();
+    let list = store
+        .find(list_name)
+        .unwrap_or_else(|| fail(json, format!("No such list: '{list_name}'.")));
+    let mut todos: Vec<&Todo> = list
+        .todos
+        .iter()
+        .filter(|t| match &cat_filter {
+            None => true,
+            Some(c) => t.category.as_ref() == Some(c),
+        })
+        .filter(|t| !pending_only || !t.done)
+        .filter(|t| !done_only || t.done)
+        .collect();
+    if let Some(key) = sort {
+        sort_todos(&mut todos, key, now_ts());
+    }
+    if json {
+        let todos: Vec<Todo> = todos.into_iter().cloned().collect();
+        emit(&Output::Todos(todos));
+        return;
+    }
     if todos.is_empty() {
-        println!("No todos.");
+        println!("No todos in '{list_name}'.");
         return;
     }
-    for t in &todos {
-        let mark = if t.done { "x" } else { " " };
-        let cat_label = match &t.category {
-            Some(c) => format!(" [{c}]"),
-            None => String::new(),
-        };
-        println!("[{mark}] #{}{}: {}", t.id, cat_label, t.text);
+    for t in todos {
+        print_todo(t);
     }
 }
 
-fn cmd_done(id: u32) {
-    let mut todos = load_todos();
-    if let Some(t) = todos.iter_mut().find(|t| t.id == id) {
-        t.done = true;
-        save_todos(&todos);
-        println!("Marked #{id} as done.");
+fn cmd_due(list_name: &str, within: Option<i64>) {
+    let store = load_store();
+    let list = store.find(list_name).unwrap_or_else(|| {
+        eprintln!("No such list: '{list_name}'.");
+        process::exit(1);
+    });
+    let now = now_ts();
+    let cutoff = now + within.unwrap_or(0);
+    let due: Vec<&Todo> = list
+        .todos
+        .iter()
+        .filter(|t| t.due.is_some_and(|d| d <= cutoff))
+        .collect();
+    if due.is_empty() {
+        println!("No due todos in '{list_name}'.");
+        return;
+    }
+    for t in due {
+        print_todo(t);
+    }
+}
+
+fn cmd_done(list_name: &str, id: u32, json: bool) {
+    let mut store = load_store();
+    let list = get_list_mut(&mut store, list_name, json);
+    let Some(t) = list.todos.iter_mut().find(|t| t.id == id) else {
+        fail(json, format!("Todo #{id} not found in '{list_name}'."));
+    };
+    t.done = true;
+    let updated = t.clone();
+    save_store(&store);
+    if json {
+        emit(&Output::Todo(updated));
+    } else {
+        println!("Marked #{id} as done in '{list_name}'.");
+    }
+}
+
+fn cmd_prio(list_name: &str, id: u32, priority: u8, json: bool) {
+    let mut store = load_store();
+    let list = get_list_mut(&mut store, list_name, json);
+    let Some(t) = list.todos.iter_mut().find(|t| t.id == id) else {
+        fail(json, format!("Todo #{id} not found in '{list_name}'."));
+    };
+    t.priority = Some(priority);
+    let updated = t.clone();
+    save_store(&store);
+    if json {
+        emit(&Output::Todo(updated));
     } else {
-        eprintln!("Todo #{id} not found.");
+        println!("Set priority {priority} for #{id} in '{list_name}'.");
+    }
+}
+
+fn cmd_remove(list_name: &str, id: u32) {
+    let mut store = load_store();
+    let list = get_list_mut(&mut store, list_name, false);
+    let len = list.todos.len();
+    list.todos.retain(|t| t.id != id);
+    if list.todos.len() == len {
+        eprintln!("Todo #{id} not found in '{list_name}'.");
+        process::exit(1);
+    }
+    save_store(&store);
+    println!("Removed #{id} from '{list_name}'.");
+}
+
+fn cmd_list_new(name: &str) {
+    let mut store = load_store();
+    if store.find(name).is_some() {
+        eprintln!("List '{name}' already exists.");
+        process::exit(1);
+    }
+    store.lists.push(TodoList::new(name));
+    save_store(&store);
+    println!("Created list: {name}");
+}
+
+fn cmd_list_switch(name: &str) {
+    let store = load_store();
+    if store.find(name).is_none() {
+        eprintln!("No such list: '{name}'.");
+        process::exit(1);
+    }
+    set_active_list_name(name);
+    println!("Switched to list: {name}");
+}
+
+fn cmd_list_ls() {
+    let store = load_store();
+    let active = active_list_name();
+    for l in &store.lists {
+        let marker = if l.name == active { "*" } else { " " };
+        println!("{marker} {} ({} todos)", l.name, l.todos.len());
+    }
+}
+
+fn cmd_export(list_name: &str, format: ExportFormat) {
+    let store = load_store();
+    let list = store.find(list_name).unwrap_or_else(|| {
+        eprintln!("No such list: '{list_name}'.");
         process::exit(1);
+    });
+    match format {
+        ExportFormat::Json => {
+            let records: Vec<TodoRecord> = list.todos.iter().map(TodoRecord::from).collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&records).expect("serialize")
+            );
+        }
+        ExportFormat::Csv => {
+            println!("id,text,done,category,due,priority");
+            for t in &list.todos {
+                let category = t
+                    .category
+                    .as_ref()
+                    .map(|c| c.to_string())
+                    .unwrap_or_default();
+                let due = t.due.map(|d| d.to_string()).unwrap_or_default();
+                let priority = t.priority.map(|p| p.to_string()).unwrap_or_default();
+                println!(
+                    "{},{},{},{},{},{}",
+                    t.id,
+                    csv_escape(&t.text),
+                    t.done,
+                    csv_escape(&category),
+                    due,
+                    priority
+                );
+            }
+        }
+        ExportFormat::Md => {
+            // Uncategorized todos are printed first, with no heading, so that
+            // re-importing the file doesn't pick up the preceding category.
+            let mut uncategorized: Vec<&Todo> = Vec::new();
+            let mut groups: Vec<(String, Vec<&Todo>)> = Vec::new();
+            for t in &list.todos {
+                match &t.category {
+                    None => uncategorized.push(t),
+                    Some(c) => {
+                        let key = c.to_string();
+                        match groups.iter_mut().find(|(k, _)| *k == key) {
+                            Some((_, items)) => items.push(t),
+                            None => groups.push((key, vec![t])),
+                        }
+                    }
+                }
+            }
+            for t in uncategorized {
+                let mark = if t.done { "x" } else { " " };
+                println!("- [{mark}] {}", t.text);
+            }
+            for (name, items) in groups {
+                println!("## {name}");
+                for t in items {
+                    let mark = if t.done { "x" } else { " " };
+                    println!("- [{mark}] {}", t.text);
+                }
+            }
+        }
     }
 }
 
-fn cmd_remove(id: u32) {
-    let mut todos = load_todos();
-    let len = todos.len();
-    todos.retain(|t| t.id != id);
-    if todos.len() == len {
-        eprintln!("Todo #{id} not found.");
+fn cmd_import(list_name: &str, path: &str) {
+    let data = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Error reading {path}: {e}");
         process::exit(1);
+    });
+    let records = parse_import(&data);
+    let mut custom_cats = load_custom_categories();
+    let mut store = load_store();
+    let list = get_list_mut(&mut store, list_name, false);
+    let mut imported = 0;
+    for rec in records {
+        let category = rec
+            .category
+            .map(|name| match parse_category(&name, &custom_cats) {
+                Ok(cat) => cat,
+                Err(_) => {
+                    custom_cats.push(name.clone());
+                    Category::Custom(name)
+                }
+            });
+        let id = list.next_id;
+        list.next_id += 1;
+        list.todos.push(Todo {
+            id,
+            text: rec.text,
+            done: rec.done,
+            category,
+            due: rec.due,
+            priority: rec.priority,
+        });
+        imported += 1;
     }
-    save_todos(&todos);
-    println!("Removed #{id}.");
+    save_custom_categories(&custom_cats);
+    save_store(&store);
+    println!("Imported {imported} todo(s) into '{list_name}'.");
 }
 
 fn cmd_category_add(name: &str) {
@@ -202,12 +958,19 @@ fn cmd_category_add(name: &str) {
     println!("Added category: {name}");
 }
 
-fn cmd_category_list() {
+fn cmd_category_list(json: bool) {
+    let cats = load_custom_categories();
+    if json {
+        emit(&Output::Categories {
+            builtin: BUILTIN_CATEGORIES.iter().map(|c| c.to_string()).collect(),
+            custom: cats,
+        });
+        return;
+    }
     println!("Built-in:");
     for c in BUILTIN_CATEGORIES {
         println!("  {c}");
     }
-    let cats = load_custom_categories();
     if !cats.is_empty() {
         println!("Custom:");
         for c in &cats {
@@ -217,19 +980,42 @@ fn cmd_category_list() {
 }
 
 fn print_usage() {
-    eprintln!("Usage: todo <command> [args]");
+    eprintln!("Usage: todo [--json] <command> [args]");
+    eprintln!();
+    eprintln!("  --json          Emit structured JSON instead of human-formatted output");
+    eprintln!("                  (supported by add, list, done, prio, category list)");
     eprintln!();
     eprintln!("Commands:");
-    eprintln!("  add [--cat <category>] <text>   Add a new todo");
-    eprintln!("  list                             List all todos");
-    eprintln!("  done <id>                        Mark a todo as done");
-    eprintln!("  remove <id>                      Remove a todo");
+    eprintln!(
+        "  add [--cat <category>] [--due <expr>] [--prio <1-3>] [--list <name>] <text>   Add a new todo"
+    );
+    eprintln!(
+        "  list [--list <name>] [--sort <prio|due|id>] [--cat <category>] [--pending|--done]"
+    );
+    eprintln!("                                   List todos in a list");
+    eprintln!("  list new <name>                  Create a new todo list");
+    eprintln!("  list switch <name>               Switch the active todo list");
+    eprintln!("  list ls                          List all todo lists");
+    eprintln!("  due [--within <expr>] [--list <name>]   List overdue (or soon-due) todos");
+    eprintln!("  done <id> [--list <name>]        Mark a todo as done");
+    eprintln!("  prio <id> <1-3> [--list <name>]  Set a todo's priority");
+    eprintln!("  remove <id> [--list <name>]      Remove a todo");
     eprintln!("  category add <name>              Add a custom category");
     eprintln!("  category list                    List all categories");
+    eprintln!("  export [--format json|md|csv] [--list <name>]   Export todos to stdout");
+    eprintln!("  import <file> [--list <name>]    Import todos from a file");
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    // A global `--json` flag can appear anywhere; strip it before parsing
+    // the rest of the command line.
+    let json = if let Some(pos) = args.iter().position(|a| a == "--json") {
+        args.remove(pos);
+        true
+    } else {
+        false
+    };
     if args.len() < 2 {
         print_usage();
         process::exit(1);
@@ -238,50 +1024,246 @@ fn main() {
     match args[1].as_str() {
         "add" => {
             if args.len() < 3 {
-                eprintln!("Usage: todo add [--cat <category>] <text>");
-                process::exit(1);
+                fail(
+                    json,
+                    "Usage: todo add [--cat <category>] [--due <expr>] [--prio <1-3>] [--list <name>] <text>",
+                );
             }
-            // Parse optional --cat flag
-            if args[2] == "--cat" {
-                if args.len() < 5 {
-                    eprintln!("Usage: todo add --cat <category> <text>");
-                    process::exit(1);
+            let mut category = None;
+            let mut due = None;
+            let mut priority = None;
+            let mut list_name = None;
+            let mut text_parts = Vec::new();
+            let mut i = 2;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--cat" => {
+                        let name = args.get(i + 1).unwrap_or_else(|| {
+                            fail(json, "Usage: todo add --cat <category> <text>")
+                        });
+                        let custom_cats = load_custom_categories();
+                        category = Some(parse_category(name, &custom_cats).unwrap_or_else(|e| {
+                            fail(
+                                json,
+                                format!(
+                                    "{e}\nUse 'todo category list' to see available categories."
+                                ),
+                            )
+                        }));
+                        i += 2;
+                    }
+                    "--due" => {
+                        let expr = args
+                            .get(i + 1)
+                            .unwrap_or_else(|| fail(json, "Usage: todo add --due <expr> <text>"));
+                        due = Some(parse_due(expr).unwrap_or_else(|e| fail(json, e)));
+                        i += 2;
+                    }
+                    "--prio" => {
+                        let n = args
+                            .get(i + 1)
+                            .unwrap_or_else(|| fail(json, "Usage: todo add --prio <1-3> <text>"));
+                        priority = Some(parse_priority(n).unwrap_or_else(|e| fail(json, e)));
+                        i += 2;
+                    }
+                    "--list" => {
+                        list_name = Some(
+                            args.get(i + 1)
+                                .unwrap_or_else(|| {
+                                    fail(json, "Usage: todo add --list <name> <text>")
+                                })
+                                .clone(),
+                        );
+                        i += 2;
+                    }
+                    part => {
+                        text_parts.push(part.to_string());
+                        i += 1;
+                    }
                 }
-                let custom_cats = load_custom_categories();
-                let category = parse_category(&args[3], &custom_cats).unwrap_or_else(|e| {
-                    eprintln!("{e}");
-                    eprintln!("Use 'todo category list' to see available categories.");
+            }
+            if text_parts.is_empty() {
+                fail(
+                    json,
+                    "Usage: todo add [--cat <category>] [--due <expr>] [--prio <1-3>] [--list <name>] <text>",
+                );
+            }
+            let list_name = list_name.unwrap_or_else(active_list_name);
+            cmd_add(
+                &list_name,
+                &text_parts.join(" "),
+                category,
+                due,
+                priority,
+                json,
+            );
+        }
+        "list" => match args.get(2).map(String::as_str) {
+            Some("new") => {
+                let name = args.get(3).unwrap_or_else(|| {
+                    eprintln!("Usage: todo list new <name>");
                     process::exit(1);
                 });
-                let text = args[4..].join(" ");
-                cmd_add(&text, Some(category));
-            } else {
-                let text = args[2..].join(" ");
-                cmd_add(&text, None);
+                cmd_list_new(name);
+            }
+            Some("switch") => {
+                let name = args.get(3).unwrap_or_else(|| {
+                    eprintln!("Usage: todo list switch <name>");
+                    process::exit(1);
+                });
+                cmd_list_switch(name);
             }
+            Some("ls") => cmd_list_ls(),
+            _ => {
+                let mut list_name = None;
+                let mut sort = None;
+                let mut cat_filter = None;
+                let mut pending_only = false;
+                let mut done_only = false;
+                let mut i = 2;
+                while i < args.len() {
+                    match args[i].as_str() {
+                        "--list" => {
+                            list_name = Some(
+                                args.get(i + 1)
+                                    .unwrap_or_else(|| fail(json, "Usage: todo list --list <name>"))
+                                    .clone(),
+                            );
+                            i += 2;
+                        }
+                        "--sort" => {
+                            let key = args.get(i + 1).unwrap_or_else(|| {
+                                fail(json, "Usage: todo list --sort <prio|due|id>")
+                            });
+                            sort = Some(parse_sort_key(key).unwrap_or_else(|e| fail(json, e)));
+                            i += 2;
+                        }
+                        "--cat" => {
+                            let name = args
+                                .get(i + 1)
+                                .unwrap_or_else(|| fail(json, "Usage: todo list --cat <category>"));
+                            let custom_cats = load_custom_categories();
+                            cat_filter = Some(
+                                parse_category(name, &custom_cats)
+                                    .unwrap_or_else(|e| fail(json, e)),
+                            );
+                            i += 2;
+                        }
+                        "--pending" => {
+                            pending_only = true;
+                            i += 1;
+                        }
+                        "--done" => {
+                            done_only = true;
+                            i += 1;
+                        }
+                        other => {
+                            fail(json, format!("Unknown argument: {other}"));
+                        }
+                    }
+                }
+                let list_name = list_name.unwrap_or_else(active_list_name);
+                cmd_list(&list_name, json, sort, cat_filter, pending_only, done_only);
+            }
+        },
+        "due" => {
+            let mut within = None;
+            let mut list_name = None;
+            let mut i = 2;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--within" => {
+                        let expr = args.get(i + 1).unwrap_or_else(|| {
+                            eprintln!("Usage: todo due [--within <expr>]");
+                            process::exit(1);
+                        });
+                        within = Some(parse_duration(expr).unwrap_or_else(|e| {
+                            eprintln!("{e}");
+                            process::exit(1);
+                        }));
+                        i += 2;
+                    }
+                    "--list" => {
+                        list_name = Some(
+                            args.get(i + 1)
+                                .unwrap_or_else(|| {
+                                    eprintln!("Usage: todo due --list <name>");
+                                    process::exit(1);
+                                })
+                                .clone(),
+                        );
+                        i += 2;
+                    }
+                    other => {
+                        eprintln!("Unknown argument: {other}");
+                        process::exit(1);
+                    }
+                }
+            }
+            let list_name = list_name.unwrap_or_else(active_list_name);
+            cmd_due(&list_name, within);
         }
-        "list" => cmd_list(),
         "done" => {
             if args.len() < 3 {
-                eprintln!("Usage: todo done <id>");
+                fail(json, "Usage: todo done <id> [--list <name>]");
+            }
+            let id: u32 = args[2]
+                .parse()
+                .unwrap_or_else(|_| fail(json, format!("Invalid id: {}", args[2])));
+            let list_name = if args.get(3).map(String::as_str) == Some("--list") {
+                args.get(4)
+                    .unwrap_or_else(|| fail(json, "Usage: todo done <id> --list <name>"))
+                    .clone()
+            } else {
+                active_list_name()
+            };
+            cmd_done(&list_name, id, json);
+        }
+        "prio" => {
+            if args.len() < 4 {
+                eprintln!("Usage: todo prio <id> <1-3> [--list <name>]");
                 process::exit(1);
             }
             let id: u32 = args[2].parse().unwrap_or_else(|_| {
                 eprintln!("Invalid id: {}", args[2]);
                 process::exit(1);
             });
-            cmd_done(id);
+            let priority = parse_priority(&args[3]).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            });
+            let list_name = if args.get(4).map(String::as_str) == Some("--list") {
+                args.get(5)
+                    .unwrap_or_else(|| {
+                        eprintln!("Usage: todo prio <id> <1-3> --list <name>");
+                        process::exit(1);
+                    })
+                    .clone()
+            } else {
+                active_list_name()
+            };
+            cmd_prio(&list_name, id, priority, json);
         }
         "remove" => {
             if args.len() < 3 {
-                eprintln!("Usage: todo remove <id>");
+                eprintln!("Usage: todo remove <id> [--list <name>]");
                 process::exit(1);
             }
             let id: u32 = args[2].parse().unwrap_or_else(|_| {
                 eprintln!("Invalid id: {}", args[2]);
                 process::exit(1);
             });
-            cmd_remove(id);
+            let list_name = if args.get(3).map(String::as_str) == Some("--list") {
+                args.get(4)
+                    .unwrap_or_else(|| {
+                        eprintln!("Usage: todo remove <id> --list <name>");
+                        process::exit(1);
+                    })
+                    .clone()
+            } else {
+                active_list_name()
+            };
+            cmd_remove(&list_name, id);
         }
         "category" => {
             if args.len() < 3 {
@@ -296,15 +1278,72 @@ fn main() {
                     }
                     cmd_category_add(&args[3]);
                 }
-                "list" => cmd_category_list(),
+                "list" => cmd_category_list(json),
                 _ => {
                     eprintln!("Unknown subcommand: category {}", args[2]);
                     process::exit(1);
                 }
             }
         }
+        "export" => {
+            let mut format = ExportFormat::Json;
+            let mut list_name = None;
+            let mut i = 2;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--format" => {
+                        let f = args.get(i + 1).unwrap_or_else(|| {
+                            eprintln!("Usage: todo export --format <json|md|csv>");
+                            process::exit(1);
+                        });
+                        format = parse_export_format(f).unwrap_or_else(|e| {
+                            eprintln!("{e}");
+                            process::exit(1);
+                        });
+                        i += 2;
+                    }
+                    "--list" => {
+                        list_name = Some(
+                            args.get(i + 1)
+                                .unwrap_or_else(|| {
+                                    eprintln!("Usage: todo export --list <name>");
+                                    process::exit(1);
+                                })
+                                .clone(),
+                        );
+                        i += 2;
+                    }
+                    other => {
+                        eprintln!("Unknown argument: {other}");
+                        process::exit(1);
+                    }
+                }
+            }
+            let list_name = list_name.unwrap_or_else(active_list_name);
+            cmd_export(&list_name, format);
+        }
+        "import" => {
+            if args.len() < 3 {
+                eprintln!("Usage: todo import <file> [--list <name>]");
+                process::exit(1);
+            }
+            let list_name = if args.get(3).map(String::as_str) == Some("--list") {
+                args.get(4)
+                    .unwrap_or_else(|| {
+                        eprintln!("Usage: todo import <file> --list <name>");
+                        process::exit(1);
+                    })
+                    .clone()
+            } else {
+                active_list_name()
+            };
+            cmd_import(&list_name, &args[2]);
+        }
         _ => {
             eprintln!("Unknown command: {}", args[1]);
+            if let Some(suggestion) = did_you_mean(&args[1], COMMANDS.iter().copied()) {
+                eprintln!("Did you mean '{suggestion}'?");
+            }
             print_usage();
             process::exit(1);
         }